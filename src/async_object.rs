@@ -0,0 +1,489 @@
+//! Copyright The NoXF/oss-rust-sdk Authors
+//! Copyright The iFREEGROUP/oss-rust-sdk Contributors
+//!
+//! Async counterparts of the blocking [`ObjectAPI`](crate::object::ObjectAPI),
+//! built on `reqwest`'s async client. The request shaping and XML parsing are
+//! shared with the blocking module so the two stay in lock-step.
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::Stream;
+use futures::TryStream;
+
+use crate::object::{
+    build_complete_multipart_body, build_delete_body, parse_content_range_total,
+    parse_delete_result, parse_list_objects, parse_upload_id, DeleteResult, Object, MAX_PART_NUMBER,
+};
+use crate::oss::RequestType;
+use crate::utils::content_md5;
+
+use super::errors::{ObjectError, OSSError};
+use super::oss::OSS;
+
+#[async_trait]
+pub trait AsyncObjectAPI {
+    async fn initiate_multipart_upload<S1>(
+        &self,
+        object_name: S1,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str> + Send;
+
+    /// Upload one part of a multipart upload and return its `ETag`. Every part
+    /// but the last must be at least [`MIN_PART_SIZE`](crate::object::MIN_PART_SIZE)
+    /// bytes; the caller is responsible for that, as the method cannot know
+    /// which part is final.
+    async fn upload_part<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        part_number: u32,
+        buf: &[u8],
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send;
+
+    async fn complete_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        parts: Vec<(u32, String)>,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send;
+
+    async fn abort_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send;
+
+    async fn get_object_range<S>(
+        &self,
+        object_name: S,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<(Vec<u8>, Option<u64>), OSSError>
+    where
+        S: AsRef<str> + Send;
+
+    async fn delete_objects<S, I>(&self, keys: I, quiet: bool) -> Result<DeleteResult, OSSError>
+    where
+        S: AsRef<str> + Send,
+        I: IntoIterator<Item = S> + Send;
+
+    async fn put_object_from_stream<B, S1>(
+        &self,
+        stream: B,
+        content_length: u64,
+        object_name: S1,
+        headers: Option<HashMap<String, String>>,
+        resources: Option<HashMap<String, Option<String>>>,
+    ) -> Result<(), OSSError>
+    where
+        B: TryStream + Send + Sync + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<B::Ok>,
+        S1: AsRef<str> + Send;
+
+    fn list_objects_stream<'s>(
+        &'s self,
+        prefix: String,
+        delimiter: String,
+        page_size: usize,
+    ) -> Pin<Box<dyn Stream<Item = Result<Object, OSSError>> + Send + 's>>;
+}
+
+/// Per-step state threaded through the [`AsyncObjectAPI::list_objects_stream`]
+/// `unfold`: the next continuation token, the objects buffered from the last
+/// page, and whether the walk is finished.
+struct ListStreamState {
+    continuation_token: Option<String>,
+    buffer: VecDeque<Object>,
+    done: bool,
+}
+
+#[async_trait]
+impl<'a> AsyncObjectAPI for OSS<'a> {
+    async fn initiate_multipart_upload<S1>(
+        &self,
+        object_name: S1,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str> + Send,
+    {
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploads", None);
+        let (host, headers) =
+            self.build_request(RequestType::Post, object_name, headers, Some(params))?;
+
+        let resp = reqwest::Client::new()
+            .post(host)
+            .headers(headers)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not initiate multipart upload, status code: {}",
+                    resp.status()
+                ),
+            }));
+        }
+
+        let xml_str = resp.text().await?;
+        parse_upload_id(&xml_str)
+    }
+
+    async fn upload_part<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        part_number: u32,
+        buf: &[u8],
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send,
+    {
+        if !(1..=MAX_PART_NUMBER).contains(&part_number) {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "part number {} out of range, must be 1..={}",
+                    part_number, MAX_PART_NUMBER
+                ),
+            }));
+        }
+
+        let part_number = part_number.to_string();
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("partNumber", Some(part_number.as_str()));
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Put,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::Client::new()
+            .put(host)
+            .headers(headers)
+            .body(buf.to_owned())
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!("can not upload part, status code: {}", resp.status()),
+            }));
+        }
+
+        resp.headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                OSSError::Object(ObjectError::MultipartError {
+                    msg: "upload part response did not contain an ETag".to_string(),
+                })
+            })
+    }
+
+    async fn complete_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        parts: Vec<(u32, String)>,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send,
+    {
+        let body = build_complete_multipart_body(&parts)?;
+
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Post,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::Client::new()
+            .post(host)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not complete multipart upload, status code: {}",
+                    resp.status()
+                ),
+            }))
+        }
+    }
+
+    async fn abort_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str> + Send,
+        S2: AsRef<str> + Send,
+    {
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Delete,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::Client::new()
+            .delete(host)
+            .headers(headers)
+            .send()
+            .await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not abort multipart upload, status code: {}",
+                    resp.status()
+                ),
+            }))
+        }
+    }
+
+    async fn get_object_range<S>(
+        &self,
+        object_name: S,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<(Vec<u8>, Option<u64>), OSSError>
+    where
+        S: AsRef<str> + Send,
+    {
+        let range = match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Range", range.as_str());
+        let (host, headers) =
+            self.build_request(RequestType::Get, object_name, Some(headers), None)?;
+
+        let resp = reqwest::Client::new()
+            .get(host)
+            .headers(headers)
+            .send()
+            .await?;
+
+        // A ranged request is only satisfied with `206 Partial Content`; a
+        // `200 OK` means the server ignored the range and sent the whole
+        // object, which we also accept.
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::GetError {
+                msg: format!("can not get object range, status code: {}", resp.status()),
+            }));
+        }
+
+        let total = resp
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_content_range_total);
+
+        let buf = resp.bytes().await?.to_vec();
+        Ok((buf, total))
+    }
+
+    async fn delete_objects<S, I>(&self, keys: I, quiet: bool) -> Result<DeleteResult, OSSError>
+    where
+        S: AsRef<str> + Send,
+        I: IntoIterator<Item = S> + Send,
+    {
+        let body = build_delete_body(keys, quiet);
+
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("delete", None);
+        let md5 = content_md5(body.as_bytes());
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Content-MD5", md5.as_str());
+        let (host, headers) =
+            self.build_request(RequestType::Post, String::new(), Some(headers), Some(params))?;
+
+        let resp = reqwest::Client::new()
+            .post(host)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::DeleteError {
+                msg: format!("can not delete objects, status code: {}", resp.status()),
+                failures: Vec::new(),
+            }));
+        }
+
+        let xml_str = resp.text().await?;
+        let result = parse_delete_result(&xml_str)?;
+        // OSS answers a batch delete with `200 OK` even when individual keys
+        // fail, reporting them in per-key `<Error>` entries. Surface those as a
+        // `DeleteError` so a partial failure is not mistaken for success.
+        if !result.errors().is_empty() {
+            return Err(OSSError::Object(ObjectError::DeleteError {
+                msg: format!("{} of the requested keys could not be deleted", result.errors().len()),
+                failures: result.errors().to_vec(),
+            }));
+        }
+        Ok(result)
+    }
+
+    async fn put_object_from_stream<B, S1>(
+        &self,
+        stream: B,
+        content_length: u64,
+        object_name: S1,
+        headers: Option<HashMap<String, String>>,
+        resources: Option<HashMap<String, Option<String>>>,
+    ) -> Result<(), OSSError>
+    where
+        B: TryStream + Send + Sync + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<B::Ok>,
+        S1: AsRef<str> + Send,
+    {
+        let (host, mut headers) =
+            self.build_request(RequestType::Put, object_name, headers, resources)?;
+        headers.insert(reqwest::header::CONTENT_LENGTH, content_length.into());
+
+        // Stream the body straight to the wire instead of materializing it, so
+        // uploading a multi-GB object uses constant memory.
+        let body = reqwest::Body::wrap_stream(stream);
+
+        let resp = reqwest::Client::new()
+            .put(host)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::PutError {
+                msg: format!("can not put object, status code: {}", resp.status()),
+            }))
+        }
+    }
+
+    fn list_objects_stream<'s>(
+        &'s self,
+        prefix: String,
+        delimiter: String,
+        page_size: usize,
+    ) -> Pin<Box<dyn Stream<Item = Result<Object, OSSError>> + Send + 's>> {
+        let state = ListStreamState {
+            continuation_token: None,
+            buffer: VecDeque::new(),
+            done: false,
+        };
+
+        let stream = futures::stream::unfold(
+            (self, prefix, delimiter, page_size, state),
+            |(oss, prefix, delimiter, page_size, mut state)| async move {
+                loop {
+                    if let Some(object) = state.buffer.pop_front() {
+                        return Some((Ok(object), (oss, prefix, delimiter, page_size, state)));
+                    }
+                    if state.done {
+                        return None;
+                    }
+
+                    let mut resources: HashMap<String, Option<String>> = HashMap::new();
+                    resources.insert("list-type".to_string(), Some("2".to_string()));
+                    resources.insert("max-keys".to_string(), Some(page_size.to_string()));
+                    if !prefix.is_empty() {
+                        resources.insert("prefix".to_string(), Some(prefix.clone()));
+                    }
+                    if !delimiter.is_empty() {
+                        resources.insert("delimiter".to_string(), Some(delimiter.clone()));
+                    }
+                    if let Some(token) = &state.continuation_token {
+                        resources.insert("continuation-token".to_string(), Some(token.clone()));
+                    }
+
+                    let fetched = match oss.build_request(
+                        RequestType::Get,
+                        String::new(),
+                        None::<HashMap<String, String>>,
+                        Some(resources),
+                    ) {
+                        Ok((host, headers)) => fetch_list_page(host, headers).await,
+                        Err(e) => Err(e),
+                    };
+                    let page = match fetched {
+                        Ok(page) => page,
+                        Err(e) => {
+                            state.done = true;
+                            return Some((Err(e), (oss, prefix, delimiter, page_size, state)));
+                        }
+                    };
+
+                    state.buffer.extend(page.objects().iter().cloned());
+                    // Page on only while the server reports more pages *and*
+                    // hands back a token to fetch them with.
+                    if page.is_truncated() && !page.next_continuation_token().is_empty() {
+                        state.continuation_token =
+                            Some(page.next_continuation_token().to_string());
+                    } else {
+                        state.done = true;
+                        state.continuation_token = None;
+                    }
+                }
+            },
+        );
+
+        Box::pin(stream)
+    }
+}
+
+/// Issue a prepared `ListObjectsV2` request and parse the response body.
+async fn fetch_list_page(
+    host: String,
+    headers: reqwest::header::HeaderMap,
+) -> Result<crate::object::ListObjects, OSSError> {
+    let resp = reqwest::Client::new()
+        .get(&host)
+        .headers(headers)
+        .send()
+        .await?;
+    let xml_str = resp.text().await?;
+    parse_list_objects(&xml_str)
+}
@@ -0,0 +1,214 @@
+//! Copyright The iFREEGROUP/oss-rust-sdk Contributors
+use quick_xml::{events::Event, Reader};
+use std::collections::HashMap;
+
+use crate::oss::RequestType;
+
+use super::errors::{ObjectError, OSSError};
+use super::oss::OSS;
+use super::utils::*;
+
+/// A single `<CORSRule>` of a bucket's CORS configuration.
+#[derive(Clone, Debug, Default)]
+pub struct CorsRule {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    expose_headers: Vec<String>,
+    max_age_seconds: Option<u64>,
+}
+
+impl CorsRule {
+    pub fn new(
+        allowed_origins: Vec<String>,
+        allowed_methods: Vec<String>,
+        allowed_headers: Vec<String>,
+        expose_headers: Vec<String>,
+        max_age_seconds: Option<u64>,
+    ) -> Self {
+        CorsRule {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            expose_headers,
+            max_age_seconds,
+        }
+    }
+
+    pub fn allowed_origins(&self) -> &Vec<String> {
+        &self.allowed_origins
+    }
+
+    pub fn allowed_methods(&self) -> &Vec<String> {
+        &self.allowed_methods
+    }
+
+    pub fn allowed_headers(&self) -> &Vec<String> {
+        &self.allowed_headers
+    }
+
+    pub fn expose_headers(&self) -> &Vec<String> {
+        &self.expose_headers
+    }
+
+    pub fn max_age_seconds(&self) -> Option<u64> {
+        self.max_age_seconds
+    }
+}
+
+pub trait BucketAPI {
+    fn put_bucket_cors(&self, rules: Vec<CorsRule>) -> Result<(), OSSError>;
+
+    fn get_bucket_cors(&self) -> Result<Vec<CorsRule>, OSSError>;
+
+    fn delete_bucket_cors(&self) -> Result<(), OSSError>;
+}
+
+impl<'a> BucketAPI for OSS<'a> {
+    fn put_bucket_cors(&self, rules: Vec<CorsRule>) -> Result<(), OSSError> {
+        let body = build_cors_body(&rules);
+
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("cors", None);
+        let md5 = content_md5(body.as_bytes());
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Content-MD5", md5.as_str());
+        let (host, headers) =
+            self.build_request(RequestType::Put, String::new(), Some(headers), Some(params))?;
+
+        let resp = reqwest::blocking::Client::new()
+            .put(host)
+            .headers(headers)
+            .body(body)
+            .send()?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::PutError {
+                msg: format!("can not put bucket cors, status code: {}", resp.status()),
+            }))
+        }
+    }
+
+    fn get_bucket_cors(&self) -> Result<Vec<CorsRule>, OSSError> {
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("cors", None);
+        let (host, headers) = self.build_request(
+            RequestType::Get,
+            String::new(),
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::blocking::Client::new()
+            .get(host)
+            .headers(headers)
+            .send()?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::GetError {
+                msg: format!("can not get bucket cors, status code: {}", resp.status()),
+            }));
+        }
+
+        let xml_str = resp.text()?;
+        let mut reader = Reader::from_str(xml_str.as_str());
+        reader.trim_text(true);
+
+        let mut rules = Vec::new();
+        let mut rule = CorsRule::default();
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                    b"AllowedOrigin" => rule
+                        .allowed_origins
+                        .push(reader.read_text(e.name())?.to_string()),
+                    b"AllowedMethod" => rule
+                        .allowed_methods
+                        .push(reader.read_text(e.name())?.to_string()),
+                    b"AllowedHeader" => rule
+                        .allowed_headers
+                        .push(reader.read_text(e.name())?.to_string()),
+                    b"ExposeHeader" => rule
+                        .expose_headers
+                        .push(reader.read_text(e.name())?.to_string()),
+                    b"MaxAgeSeconds" => {
+                        rule.max_age_seconds = reader.read_text(e.name())?.parse::<u64>().ok()
+                    }
+                    _ => (),
+                },
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"CORSRule" => {
+                    rules.push(std::mem::take(&mut rule));
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+                _ => (),
+            }
+        }
+        Ok(rules)
+    }
+
+    fn delete_bucket_cors(&self) -> Result<(), OSSError> {
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("cors", None);
+        let (host, headers) = self.build_request(
+            RequestType::Delete,
+            String::new(),
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::blocking::Client::new()
+            .delete(host)
+            .headers(headers)
+            .send()?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::DeleteError {
+                msg: format!("can not delete bucket cors, status code: {}", resp.status()),
+                failures: Vec::new(),
+            }))
+        }
+    }
+}
+
+/// Serialize a `<CORSConfiguration>` body with one `<CORSRule>` per rule.
+fn build_cors_body(rules: &[CorsRule]) -> String {
+    let mut body = String::from("<CORSConfiguration>");
+    for rule in rules {
+        body.push_str("<CORSRule>");
+        for origin in &rule.allowed_origins {
+            body.push_str(&format!(
+                "<AllowedOrigin>{}</AllowedOrigin>",
+                xml_escape(origin)
+            ));
+        }
+        for method in &rule.allowed_methods {
+            body.push_str(&format!(
+                "<AllowedMethod>{}</AllowedMethod>",
+                xml_escape(method)
+            ));
+        }
+        for header in &rule.allowed_headers {
+            body.push_str(&format!(
+                "<AllowedHeader>{}</AllowedHeader>",
+                xml_escape(header)
+            ));
+        }
+        for header in &rule.expose_headers {
+            body.push_str(&format!(
+                "<ExposeHeader>{}</ExposeHeader>",
+                xml_escape(header)
+            ));
+        }
+        if let Some(max_age) = rule.max_age_seconds {
+            body.push_str(&format!("<MaxAgeSeconds>{}</MaxAgeSeconds>", max_age));
+        }
+        body.push_str("</CORSRule>");
+    }
+    body.push_str("</CORSConfiguration>");
+    body
+}
@@ -0,0 +1,34 @@
+//! Copyright The NoXF/oss-rust-sdk Authors
+//! Copyright The iFREEGROUP/oss-rust-sdk Contributors
+use reqwest::header::{InvalidHeaderName, InvalidHeaderValue};
+
+use crate::object::DeletedError;
+
+#[derive(Debug, From)]
+pub enum OSSError {
+    Object(ObjectError),
+    Reqwest(reqwest::Error),
+    Io(std::io::Error),
+    Xml(quick_xml::Error),
+    Utf8(std::string::FromUtf8Error),
+    InvalidHeaderName(InvalidHeaderName),
+    InvalidHeaderValue(InvalidHeaderValue),
+}
+
+#[derive(Debug)]
+pub enum ObjectError {
+    GetError { msg: String },
+    PutError { msg: String },
+    CopyError { msg: String },
+    /// Raised when a delete request fails. For a bulk delete the `failures`
+    /// field carries the per-key `<Error>` entries OSS reported; it is empty
+    /// for a single-object delete or when the server rejects the whole request
+    /// with a non-success status. Transport errors surface as
+    /// [`OSSError::Reqwest`] instead.
+    DeleteError {
+        msg: String,
+        failures: Vec<DeletedError>,
+    },
+    MultipartError { msg: String },
+    DecryptionError { msg: String },
+}
@@ -6,6 +6,8 @@ extern crate log;
 
 pub mod async_object;
 pub mod async_service;
+pub mod bucket;
+pub mod encryption;
 pub mod errors;
 // pub mod object;
 pub mod oss;
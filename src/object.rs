@@ -1,11 +1,14 @@
 //! Copyright The NoXF/oss-rust-sdk Authors
 //! Copyright The iFREEGROUP/oss-rust-sdk Contributors
 use quick_xml::{events::Event, Reader};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufReader, Read};
 
+use crate::encryption::Encryptor;
 use crate::oss::RequestType;
 
-use super::errors::{OSSError};
+use super::errors::{ObjectError, OSSError};
 use super::oss::OSS;
 use super::utils::*;
 
@@ -17,11 +20,14 @@ pub struct ListObjects {
     marker: String,
     max_keys: String,
     is_truncated: bool,
+    next_continuation_token: String,
+    common_prefixes: Vec<String>,
 
     objects: Vec<Object>,
 }
 
 impl ListObjects {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         bucket_name: String,
         delimiter: String,
@@ -29,6 +35,8 @@ impl ListObjects {
         marker: String,
         max_keys: String,
         is_truncated: bool,
+        next_continuation_token: String,
+        common_prefixes: Vec<String>,
 
         objects: Vec<Object>,
     ) -> Self {
@@ -39,6 +47,8 @@ impl ListObjects {
             marker,
             max_keys,
             is_truncated,
+            next_continuation_token,
+            common_prefixes,
 
             objects,
         }
@@ -68,6 +78,19 @@ impl ListObjects {
         self.is_truncated
     }
 
+    /// Continuation token returned by a `list-type=2` listing, to be passed as
+    /// `continuation-token` on the next request. Empty when the listing is not
+    /// truncated or the V1 protocol was used.
+    pub fn next_continuation_token(&self) -> &str {
+        &self.next_continuation_token
+    }
+
+    /// Keys rolled up by the `delimiter` into `<CommonPrefixes>`, i.e. the
+    /// "subdirectories" under the requested prefix.
+    pub fn common_prefixes(&self) -> &Vec<String> {
+        &self.common_prefixes
+    }
+
     pub fn objects(&self) -> &Vec<Object> {
         &self.objects
     }
@@ -143,6 +166,58 @@ impl Object {
     }
 }
 
+/// Highest part number OSS accepts for a single multipart upload.
+pub const MAX_PART_NUMBER: u32 = 10_000;
+
+/// Minimum size, in bytes, of every part except the last one in a multipart
+/// upload. OSS rejects a `CompleteMultipartUpload` whose non-final parts are
+/// smaller than this with `EntityTooSmall`. [`ObjectAPI::upload_part`] cannot
+/// tell which part will turn out to be the last, so it does not enforce the
+/// floor itself — honouring it is the caller's responsibility when slicing the
+/// payload into parts.
+pub const MIN_PART_SIZE: usize = 100 * 1024;
+
+/// Outcome of a [`ObjectAPI::delete_objects`] bulk delete, parsed from the
+/// `<DeleteResult>` response: the keys OSS reports as successfully removed and
+/// a per-key list of failures.
+#[derive(Clone, Debug, Default)]
+pub struct DeleteResult {
+    deleted: Vec<String>,
+    errors: Vec<DeletedError>,
+}
+
+impl DeleteResult {
+    pub fn deleted(&self) -> &Vec<String> {
+        &self.deleted
+    }
+
+    pub fn errors(&self) -> &Vec<DeletedError> {
+        &self.errors
+    }
+}
+
+/// A single `<Error>` entry in a bulk delete response.
+#[derive(Clone, Debug)]
+pub struct DeletedError {
+    key: String,
+    code: String,
+    message: String,
+}
+
+impl DeletedError {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
 pub trait ObjectAPI {
     fn list_object<S, H, R>(&self, headers: H, resources: R) -> Result<ListObjects, OSSError>
     where
@@ -166,6 +241,15 @@ pub trait ObjectAPI {
     where
         S: AsRef<str>;
 
+    fn get_object_range<S>(
+        &self,
+        object_name: S,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<(Vec<u8>, Option<u64>), OSSError>
+    where
+        S: AsRef<str>;
+
     fn put_object_from_file<S1, S2, S3, H, R>(
         &self,
         file: S1,
@@ -193,6 +277,21 @@ pub trait ObjectAPI {
         H: Into<Option<HashMap<S2, S2>>>,
         R: Into<Option<HashMap<S2, Option<S2>>>>;
 
+    fn put_object_from_reader<RD, S1, S2, H, R>(
+        &self,
+        reader: RD,
+        content_length: u64,
+        object_name: S1,
+        headers: H,
+        resources: R,
+    ) -> Result<(), OSSError>
+    where
+        RD: Read + Send + 'static,
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>,
+        R: Into<Option<HashMap<S2, Option<S2>>>>;
+
     fn copy_object_from_object<S1, S2, S3, H, R>(
         &self,
         src: S1,
@@ -210,6 +309,154 @@ pub trait ObjectAPI {
     fn delete_object<S>(&self, object_name: S) -> Result<(), OSSError>
     where
         S: AsRef<str>;
+
+    fn put_object_encrypted<RD, S1, S2, H>(
+        &self,
+        encryptor: &Encryptor,
+        reader: RD,
+        content_length: u64,
+        object_name: S1,
+        headers: H,
+    ) -> Result<(), OSSError>
+    where
+        RD: Read + Send + 'static,
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>;
+
+    fn get_object_decrypted<S1>(
+        &self,
+        encryptor: &Encryptor,
+        object_name: S1,
+    ) -> Result<Vec<u8>, OSSError>
+    where
+        S1: AsRef<str>;
+
+    fn delete_objects<S, I>(&self, keys: I, quiet: bool) -> Result<DeleteResult, OSSError>
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>;
+
+    fn initiate_multipart_upload<S1, S2, H>(
+        &self,
+        object_name: S1,
+        headers: H,
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>;
+
+    /// Upload one part of a multipart upload and return its `ETag`. Every part
+    /// but the last must be at least [`MIN_PART_SIZE`] bytes; the caller is
+    /// responsible for that, as the method cannot know which part is final.
+    fn upload_part<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        part_number: u32,
+        buf: &[u8],
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>;
+
+    fn complete_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        parts: Vec<(u32, String)>,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>;
+
+    fn abort_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>;
+
+    fn list_objects_iter<'s, S1, S2>(
+        &'s self,
+        prefix: S1,
+        delimiter: S2,
+        page_size: usize,
+    ) -> ListObjectsIter<'s, Self>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        Self: Sized;
+}
+
+/// An iterator over every [`Object`] under a prefix, transparently re-issuing
+/// `ListObjectsV2` requests with the `NextContinuationToken` of the previous
+/// page as each page is exhausted. Iteration stops once a page comes back with
+/// `is_truncated` false. Each item is a `Result` so a failing continuation
+/// request surfaces to the caller instead of silently ending the walk.
+pub struct ListObjectsIter<'s, T: ObjectAPI> {
+    api: &'s T,
+    prefix: String,
+    delimiter: String,
+    page_size: usize,
+    continuation_token: Option<String>,
+    buffer: VecDeque<Object>,
+    done: bool,
+}
+
+impl<'s, T: ObjectAPI> Iterator for ListObjectsIter<'s, T> {
+    type Item = Result<Object, OSSError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(object) = self.buffer.pop_front() {
+                return Some(Ok(object));
+            }
+            if self.done {
+                return None;
+            }
+
+            let mut resources: HashMap<String, Option<String>> = HashMap::new();
+            resources.insert("list-type".to_string(), Some("2".to_string()));
+            resources.insert("max-keys".to_string(), Some(self.page_size.to_string()));
+            if !self.prefix.is_empty() {
+                resources.insert("prefix".to_string(), Some(self.prefix.clone()));
+            }
+            if !self.delimiter.is_empty() {
+                resources.insert("delimiter".to_string(), Some(self.delimiter.clone()));
+            }
+            if let Some(token) = &self.continuation_token {
+                resources.insert("continuation-token".to_string(), Some(token.clone()));
+            }
+
+            let page = match self
+                .api
+                .list_object(None::<HashMap<String, String>>, Some(resources))
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.buffer.extend(page.objects().iter().cloned());
+            // Keep paging only while the server both reports more pages and
+            // hands back a token to fetch them with; a truncated page with an
+            // empty `NextContinuationToken` would otherwise loop forever.
+            if page.is_truncated() && !page.next_continuation_token().is_empty() {
+                self.continuation_token = Some(page.next_continuation_token().to_string());
+            } else {
+                self.done = true;
+                self.continuation_token = None;
+            }
+            // A delimiter page may carry only `<CommonPrefixes>` and zero
+            // objects; loop to fetch the next page instead of ending the walk.
+        }
+    }
 }
 
 impl<'a> ObjectAPI for OSS<'a> {
@@ -228,87 +475,7 @@ impl<'a> ObjectAPI for OSS<'a> {
             .send()?;
 
         let xml_str = resp.text()?;
-        let mut result = Vec::new();
-        let mut reader = Reader::from_str(xml_str.as_str());
-        reader.trim_text(true);
-
-        let mut bucket_name = String::new();
-        let mut prefix = String::new();
-        let mut marker = String::new();
-        let mut max_keys = String::new();
-        let mut delimiter = String::new();
-        let mut is_truncated = false;
-
-        let mut key = String::new();
-        let mut last_modified = String::new();
-        let mut etag = String::new();
-        let mut r#type = String::new();
-        let mut size = 0usize;
-        let mut storage_class = String::new();
-        let mut owner_id = String::new();
-        let mut owner_display_name = String::new();
-
-        let list_objects;
-
-        loop {
-            match reader.read_event() {
-                Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                    b"Name" => bucket_name = reader.read_text(e.name())?.to_string(),
-                    b"Prefix" => prefix = reader.read_text(e.name())?.to_string(),
-                    b"Marker" => marker = reader.read_text(e.name())?.to_string(),
-                    b"MaxKeys" => max_keys = reader.read_text(e.name())?.to_string(),
-                    b"Delimiter" => delimiter = reader.read_text(e.name())?.to_string(),
-                    b"IsTruncated" => {
-                        is_truncated = reader.read_text(e.name())? == "true"
-                    }
-                    b"Contents" => {
-                        // do nothing
-                    }
-                    b"Key" => key = reader.read_text(e.name())?.to_string(),
-                    b"LastModified" => last_modified = reader.read_text(e.name())?.to_string(),
-                    b"ETag" => etag = reader.read_text(e.name())?.to_string(),
-                    b"Type" => r#type = reader.read_text(e.name())?.to_string(),
-                    b"Size" => size = reader.read_text(e.name())?.parse::<usize>().unwrap(),
-                    b"StorageClass" => storage_class = reader.read_text(e.name())?.to_string(),
-                    b"Owner" => {
-                        // do nothing
-                    }
-                    b"ID" => owner_id = reader.read_text(e.name())?.to_string(),
-                    b"DisplayName" => owner_display_name = reader.read_text(e.name())?.to_string(),
-
-                    _ => (),
-                },
-
-                Ok(Event::End(ref e)) if e.name().as_ref() == b"Contents" => {
-                    let object = Object::new(
-                        key.clone(),
-                        last_modified.clone(),
-                        size,
-                        etag.clone(),
-                        r#type.clone(),
-                        storage_class.clone(),
-                        owner_id.clone(),
-                        owner_display_name.clone(),
-                    );
-                    result.push(object);
-                }
-                Ok(Event::Eof) => {
-                    list_objects = ListObjects::new(
-                        bucket_name,
-                        delimiter,
-                        prefix,
-                        marker,
-                        max_keys,
-                        is_truncated,
-                        result,
-                    );
-                    break;
-                } // exits the loop when reaching end of file
-                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-                _ => (), // There are several other `Event`s we do not consider here
-            }
-        }
-        Ok(list_objects)
+        parse_list_objects(&xml_str)
     }
 
     fn get_object<S1, S2, H, R>(
@@ -342,6 +509,49 @@ impl<'a> ObjectAPI for OSS<'a> {
         }
     }
 
+    fn get_object_range<S>(
+        &self,
+        object_name: S,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<(Vec<u8>, Option<u64>), OSSError>
+    where
+        S: AsRef<str>,
+    {
+        let range = match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Range", range.as_str());
+        let (host, headers) =
+            self.build_request(RequestType::Get, object_name, Some(headers), None)?;
+
+        let mut resp = reqwest::blocking::Client::new()
+            .get(host)
+            .headers(headers)
+            .send()?;
+
+        // A ranged request is only satisfied with `206 Partial Content`; a
+        // `200 OK` means the server ignored the range and sent the whole
+        // object, which we also accept.
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::GetError {
+                msg: format!("can not get object range, status code: {}", resp.status()),
+            }));
+        }
+
+        let total = resp
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_content_range_total);
+
+        let mut buf: Vec<u8> = vec![];
+        resp.copy_to(&mut buf)?;
+        Ok((buf, total))
+    }
+
     fn get_object_acl<S>(&self, object_name: S) -> Result<String, OSSError>
     where
         S: AsRef<str>,
@@ -381,16 +591,40 @@ impl<'a> ObjectAPI for OSS<'a> {
         S3: AsRef<str>,
         H: Into<Option<HashMap<S3, S3>>>,
         R: Into<Option<HashMap<S3, Option<S3>>>>,
+    {
+        let file = File::open(file.as_ref())?;
+        let content_length = file.metadata()?.len();
+        let reader = BufReader::new(file);
+        self.put_object_from_reader(reader, content_length, object_name, headers, resources)
+    }
+
+    fn put_object_from_reader<RD, S1, S2, H, R>(
+        &self,
+        reader: RD,
+        content_length: u64,
+        object_name: S1,
+        headers: H,
+        resources: R,
+    ) -> Result<(), OSSError>
+    where
+        RD: Read + Send + 'static,
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>,
+        R: Into<Option<HashMap<S2, Option<S2>>>>,
     {
         let (host, headers) =
             self.build_request(RequestType::Put, object_name, headers, resources)?;
 
-        let buf = load_file(file)?;
+        // Stream the reader straight into the request body instead of reading
+        // the whole payload into a `Vec<u8>`, so uploading a multi-GB file uses
+        // constant memory.
+        let body = reqwest::blocking::Body::sized(reader, content_length);
 
         let resp = reqwest::blocking::Client::new()
             .put(host)
             .headers(headers)
-            .body(buf)
+            .body(body)
             .send()?;
 
         if resp.status().is_success() {
@@ -483,7 +717,507 @@ impl<'a> ObjectAPI for OSS<'a> {
         } else {
             Err(OSSError::Object(ObjectError::DeleteError {
                 msg: format!("can not delete object, status code: {}", resp.status()),
+                failures: Vec::new(),
+            }))
+        }
+    }
+
+    fn put_object_encrypted<RD, S1, S2, H>(
+        &self,
+        encryptor: &Encryptor,
+        reader: RD,
+        content_length: u64,
+        object_name: S1,
+        headers: H,
+    ) -> Result<(), OSSError>
+    where
+        RD: Read + Send + 'static,
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>,
+    {
+        // Seal the plaintext chunk by chunk as it is uploaded, so the body is
+        // never held in memory in full, and size the request with the known
+        // ciphertext length up front.
+        let enc = encryptor.encrypt_reader(reader);
+        let nonce_b64 = enc.nonce_b64();
+        let ciphertext_length = Encryptor::encrypted_len(content_length);
+
+        // Record the algorithm and nonce as user metadata so the object stays
+        // self-describing, without clobbering any caller-supplied headers.
+        let mut merged: HashMap<String, String> = HashMap::new();
+        if let Some(headers) = headers.into() {
+            for (key, val) in headers.iter() {
+                merged.insert(key.as_ref().to_string(), val.as_ref().to_string());
+            }
+        }
+        merged.extend(encryptor.metadata_headers(&nonce_b64));
+
+        self.put_object_from_reader(
+            enc,
+            ciphertext_length,
+            object_name,
+            Some(merged),
+            None::<HashMap<String, Option<String>>>,
+        )
+    }
+
+    fn get_object_decrypted<S1>(
+        &self,
+        encryptor: &Encryptor,
+        object_name: S1,
+    ) -> Result<Vec<u8>, OSSError>
+    where
+        S1: AsRef<str>,
+    {
+        let blob = self.get_object(
+            object_name,
+            None::<HashMap<String, String>>,
+            None::<HashMap<String, Option<String>>>,
+        )?;
+        encryptor.decrypt_all(&blob)
+    }
+
+    fn delete_objects<S, I>(&self, keys: I, quiet: bool) -> Result<DeleteResult, OSSError>
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = S>,
+    {
+        let body = build_delete_body(keys, quiet);
+
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("delete", None);
+        let md5 = content_md5(body.as_bytes());
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Content-MD5", md5.as_str());
+        let (host, headers) =
+            self.build_request(RequestType::Post, String::new(), Some(headers), Some(params))?;
+
+        let resp = reqwest::blocking::Client::new()
+            .post(host)
+            .headers(headers)
+            .body(body)
+            .send()?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::DeleteError {
+                msg: format!("can not delete objects, status code: {}", resp.status()),
+                failures: Vec::new(),
+            }));
+        }
+
+        let xml_str = resp.text()?;
+        let result = parse_delete_result(&xml_str)?;
+        // OSS answers a batch delete with `200 OK` even when individual keys
+        // fail, reporting them in per-key `<Error>` entries. Surface those as a
+        // `DeleteError` so a partial failure is not mistaken for success.
+        if !result.errors().is_empty() {
+            return Err(OSSError::Object(ObjectError::DeleteError {
+                msg: format!("{} of the requested keys could not be deleted", result.errors().len()),
+                failures: result.errors().to_vec(),
+            }));
+        }
+        Ok(result)
+    }
+
+    fn initiate_multipart_upload<S1, S2, H>(
+        &self,
+        object_name: S1,
+        headers: H,
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        H: Into<Option<HashMap<S2, S2>>>,
+    {
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploads", None);
+        let (host, headers) =
+            self.build_request(RequestType::Post, object_name, headers, Some(params))?;
+
+        let resp = reqwest::blocking::Client::new()
+            .post(host)
+            .headers(headers)
+            .send()?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not initiate multipart upload, status code: {}",
+                    resp.status()
+                ),
+            }));
+        }
+
+        let xml_str = resp.text()?;
+        parse_upload_id(&xml_str)
+    }
+
+    fn upload_part<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        part_number: u32,
+        buf: &[u8],
+    ) -> Result<String, OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        if !(1..=MAX_PART_NUMBER).contains(&part_number) {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "part number {} out of range, must be 1..={}",
+                    part_number, MAX_PART_NUMBER
+                ),
+            }));
+        }
+
+        let part_number = part_number.to_string();
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("partNumber", Some(part_number.as_str()));
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Put,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::blocking::Client::new()
+            .put(host)
+            .headers(headers)
+            .body(buf.to_owned())
+            .send()?;
+
+        if !resp.status().is_success() {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!("can not upload part, status code: {}", resp.status()),
+            }));
+        }
+
+        resp.headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                OSSError::Object(ObjectError::MultipartError {
+                    msg: "upload part response did not contain an ETag".to_string(),
+                })
+            })
+    }
+
+    fn complete_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+        parts: Vec<(u32, String)>,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        let body = build_complete_multipart_body(&parts)?;
+
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Post,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::blocking::Client::new()
+            .post(host)
+            .headers(headers)
+            .body(body)
+            .send()?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not complete multipart upload, status code: {}",
+                    resp.status()
+                ),
+            }))
+        }
+    }
+
+    fn abort_multipart_upload<S1, S2>(
+        &self,
+        object_name: S1,
+        upload_id: S2,
+    ) -> Result<(), OSSError>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        let upload_id = upload_id.as_ref();
+        let mut params: HashMap<&str, Option<&str>> = HashMap::new();
+        params.insert("uploadId", Some(upload_id));
+        let (host, headers) = self.build_request(
+            RequestType::Delete,
+            object_name,
+            None::<HashMap<String, String>>,
+            Some(params),
+        )?;
+
+        let resp = reqwest::blocking::Client::new()
+            .delete(host)
+            .headers(headers)
+            .send()?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!(
+                    "can not abort multipart upload, status code: {}",
+                    resp.status()
+                ),
             }))
         }
     }
+
+    fn list_objects_iter<'s, S1, S2>(
+        &'s self,
+        prefix: S1,
+        delimiter: S2,
+        page_size: usize,
+    ) -> ListObjectsIter<'s, Self>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        Self: Sized,
+    {
+        ListObjectsIter {
+            api: self,
+            prefix: prefix.into(),
+            delimiter: delimiter.into(),
+            page_size,
+            continuation_token: None,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Serialize the `<Delete>` request body for a bulk delete, escaping each key.
+pub(crate) fn build_delete_body<S, I>(keys: I, quiet: bool) -> String
+where
+    S: AsRef<str>,
+    I: IntoIterator<Item = S>,
+{
+    let mut body = format!("<Delete><Quiet>{}</Quiet>", quiet);
+    for key in keys {
+        body.push_str(&format!(
+            "<Object><Key>{}</Key></Object>",
+            xml_escape(key.as_ref())
+        ));
+    }
+    body.push_str("</Delete>");
+    body
+}
+
+/// Parse a `ListObjects`/`ListObjectsV2` response body into a [`ListObjects`].
+/// Shared by the blocking [`ObjectAPI::list_object`] and the async listing.
+pub(crate) fn parse_list_objects(xml_str: &str) -> Result<ListObjects, OSSError> {
+    let mut result = Vec::new();
+    let mut reader = Reader::from_str(xml_str);
+    reader.trim_text(true);
+
+    let mut bucket_name = String::new();
+    let mut prefix = String::new();
+    let mut marker = String::new();
+    let mut max_keys = String::new();
+    let mut delimiter = String::new();
+    let mut is_truncated = false;
+    let mut next_continuation_token = String::new();
+    let mut common_prefixes = Vec::new();
+    let mut in_common_prefixes = false;
+
+    let mut key = String::new();
+    let mut last_modified = String::new();
+    let mut etag = String::new();
+    let mut r#type = String::new();
+    let mut size = 0usize;
+    let mut storage_class = String::new();
+    let mut owner_id = String::new();
+    let mut owner_display_name = String::new();
+
+    let list_objects;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"Name" => bucket_name = reader.read_text(e.name())?.to_string(),
+                b"Prefix" if in_common_prefixes => {
+                    common_prefixes.push(reader.read_text(e.name())?.to_string())
+                }
+                b"Prefix" => prefix = reader.read_text(e.name())?.to_string(),
+                b"Marker" => marker = reader.read_text(e.name())?.to_string(),
+                b"MaxKeys" => max_keys = reader.read_text(e.name())?.to_string(),
+                b"Delimiter" => delimiter = reader.read_text(e.name())?.to_string(),
+                b"IsTruncated" => is_truncated = reader.read_text(e.name())? == "true",
+                b"NextContinuationToken" => {
+                    next_continuation_token = reader.read_text(e.name())?.to_string()
+                }
+                b"CommonPrefixes" => in_common_prefixes = true,
+                b"Contents" => {
+                    // do nothing
+                }
+                b"Key" => key = reader.read_text(e.name())?.to_string(),
+                b"LastModified" => last_modified = reader.read_text(e.name())?.to_string(),
+                b"ETag" => etag = reader.read_text(e.name())?.to_string(),
+                b"Type" => r#type = reader.read_text(e.name())?.to_string(),
+                b"Size" => size = reader.read_text(e.name())?.parse::<usize>().unwrap(),
+                b"StorageClass" => storage_class = reader.read_text(e.name())?.to_string(),
+                b"Owner" => {
+                    // do nothing
+                }
+                b"ID" => owner_id = reader.read_text(e.name())?.to_string(),
+                b"DisplayName" => owner_display_name = reader.read_text(e.name())?.to_string(),
+
+                _ => (),
+            },
+
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"Contents" => {
+                let object = Object::new(
+                    key.clone(),
+                    last_modified.clone(),
+                    size,
+                    etag.clone(),
+                    r#type.clone(),
+                    storage_class.clone(),
+                    owner_id.clone(),
+                    owner_display_name.clone(),
+                );
+                result.push(object);
+            }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"CommonPrefixes" => {
+                in_common_prefixes = false;
+            }
+            Ok(Event::Eof) => {
+                list_objects = ListObjects::new(
+                    bucket_name,
+                    delimiter,
+                    prefix,
+                    marker,
+                    max_keys,
+                    is_truncated,
+                    next_continuation_token,
+                    common_prefixes,
+                    result,
+                );
+                break;
+            } // exits the loop when reaching end of file
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (), // There are several other `Event`s we do not consider here
+        }
+    }
+    Ok(list_objects)
+}
+
+/// Parse a bulk-delete `<DeleteResult>` response body into a [`DeleteResult`].
+pub(crate) fn parse_delete_result(xml_str: &str) -> Result<DeleteResult, OSSError> {
+    let mut reader = Reader::from_str(xml_str);
+    reader.trim_text(true);
+
+    let mut result = DeleteResult::default();
+    let mut key = String::new();
+    let mut code = String::new();
+    let mut message = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"Key" => key = reader.read_text(e.name())?.to_string(),
+                b"Code" => code = reader.read_text(e.name())?.to_string(),
+                b"Message" => message = reader.read_text(e.name())?.to_string(),
+                _ => (),
+            },
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"Deleted" => result.deleted.push(std::mem::take(&mut key)),
+                b"Error" => result.errors.push(DeletedError {
+                    key: std::mem::take(&mut key),
+                    code: std::mem::take(&mut code),
+                    message: std::mem::take(&mut message),
+                }),
+                _ => (),
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (),
+        }
+    }
+    Ok(result)
+}
+
+/// Parse the `<UploadId>` out of an `InitiateMultipartUploadResult` body.
+pub(crate) fn parse_upload_id(xml_str: &str) -> Result<String, OSSError> {
+    let mut reader = Reader::from_str(xml_str);
+    reader.trim_text(true);
+    let mut upload_id = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"UploadId" => {
+                upload_id = reader.read_text(e.name())?.to_string();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (),
+        }
+    }
+
+    if upload_id.is_empty() {
+        return Err(OSSError::Object(ObjectError::MultipartError {
+            msg: "response did not contain an UploadId".to_string(),
+        }));
+    }
+    Ok(upload_id)
+}
+
+/// Parse the total object size out of a `Content-Range` header of the form
+/// `bytes 0-1023/146515`. Returns `None` when the total is unknown (`*`) or the
+/// header is malformed.
+pub(crate) fn parse_content_range_total(value: &str) -> Option<u64> {
+    let total = value.rsplit('/').next()?.trim();
+    if total == "*" {
+        None
+    } else {
+        total.parse::<u64>().ok()
+    }
+}
+
+/// Serialize the `<CompleteMultipartUpload>` request body from the parts the
+/// caller collected. The parts must be listed in strictly ascending order by
+/// part number with non-empty `ETag`s, otherwise a [`ObjectError::MultipartError`]
+/// is returned.
+pub(crate) fn build_complete_multipart_body(parts: &[(u32, String)]) -> Result<String, OSSError> {
+    let mut last = 0u32;
+    let mut body = String::from("<CompleteMultipartUpload>");
+    for (number, etag) in parts {
+        if *number <= last {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!("parts must be in ascending order, got {} after {}", number, last),
+            }));
+        }
+        if etag.is_empty() {
+            return Err(OSSError::Object(ObjectError::MultipartError {
+                msg: format!("part {} has an empty ETag", number),
+            }));
+        }
+        last = *number;
+        body.push_str(&format!(
+            "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+            number,
+            xml_escape(etag)
+        ));
+    }
+    body.push_str("</CompleteMultipartUpload>");
+    Ok(body)
 }
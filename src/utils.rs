@@ -1,22 +1,32 @@
 //! Copyright The NoXF/oss-rust-sdk Authors
 use super::errors::OSSError;
+use base64::Engine;
 use reqwest::header::{HeaderMap, HeaderName};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufReader, Read};
 
-#[allow(dead_code)]
-#[inline]
-pub fn load_file<S>(p: S) -> Result<Vec<u8>, OSSError>
-where
-    S: AsRef<str>,
-{
-    let p = p.as_ref();
-    let f = File::open(p)?;
-    let mut f = BufReader::new(f);
-    let mut s = Vec::new();
-    f.read_to_end(&mut s)?;
-    Ok(s)
+/// Compute the base64-encoded MD5 digest of `body`, as required by the
+/// `Content-MD5` header on OSS bulk operations (batch delete, CORS, ...).
+pub fn content_md5(body: &[u8]) -> String {
+    let digest = md5::compute(body);
+    base64::engine::general_purpose::STANDARD.encode(digest.0)
+}
+
+/// Escape the five XML predefined entities so arbitrary object keys, ETags and
+/// header values can be interpolated into a hand-built request body without
+/// producing a malformed or injectable document.
+pub fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
 }
 
 pub fn to_headers<S>(hashmap: HashMap<S, S>) -> Result<HeaderMap, OSSError>